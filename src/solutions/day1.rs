@@ -4,17 +4,19 @@ use std::ops::AddAssign;
 use std::ops::Sub;
 use std::ops::Add;
 use std::error::Error;
+use std::num::ParseIntError;
+use std::str::FromStr;
 use std::result;
 use std::fmt;
-use std::cmp::{max, min};
-use std::collections::HashSet;
+use std::collections::{HashSet, HashMap};
 use rayon::prelude::*;
+use super::pathfind;
 // use itertools::Itertools;
 
 type Result<T> = result::Result<T, String>;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-enum Compass {
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Compass {
     North,
     West,
     South,
@@ -22,7 +24,7 @@ enum Compass {
 }
 
 impl Compass {
-    fn turn_left(self) -> Self {
+    pub(crate) fn turn_left(self) -> Self {
         match self {
             Compass::North => Compass::West,
             Compass::West  => Compass::South,
@@ -31,7 +33,7 @@ impl Compass {
         }
     }
 
-    fn turn_right(self) -> Self {
+    pub(crate) fn turn_right(self) -> Self {
         match self {
             Compass::North => Compass::East,
             Compass::East  => Compass::South,
@@ -71,41 +73,199 @@ impl Default for Compass {
     fn default() -> Compass { Compass::North }
 }
 
-#[derive(Default, Copy, Clone, PartialEq, Eq, Hash, Debug)]
-struct Position(i32, i32);
+impl Compass {
+    // The axis/sign pair a compass direction steps along, in terms of the
+    // dimension-agnostic `Heading`.
+    fn heading(self) -> Heading {
+        match self {
+            Compass::North => Heading { axis: 1, sign:  1 },
+            Compass::West  => Heading { axis: 0, sign: -1 },
+            Compass::South => Heading { axis: 1, sign: -1 },
+            Compass::East  => Heading { axis: 0, sign:  1 },
+        }
+    }
+}
+
+// The unit step a walker takes when facing `compass`.  Shared by the
+// breadcrumb walks in `solve2_hash`/`Path` and the pathfinding module so the
+// heading convention lives in exactly one place.
+pub(crate) fn step_delta(compass: Compass) -> Position {
+    compass.heading().delta()
+}
 
-impl AddAssign for Position {
-    fn add_assign(&mut self, other: Position) {
-        *self = Position(self.0 + other.0, self.1 + other.1);
+/// A fixed-size integer vector, generic over its dimension `N`.
+///
+/// `Position` is the 2D specialization the day-1 walk is built on; a larger
+/// `N` lets the same arithmetic and breadcrumb logic model 3D (and higher)
+/// trajectories without a separate type.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct Vector<const N: usize>(pub(crate) [i32; N]);
+
+pub(crate) type Position = Vector<2>;
+
+impl<const N: usize> Default for Vector<N> {
+    fn default() -> Vector<N> { Vector([0; N]) }
+}
+
+impl<const N: usize> Vector<N> {
+    /// The unit step along `axis` with the given `sign` (`+1` or `-1`).
+    pub(crate) fn unit(axis: usize, sign: i32) -> Vector<N> {
+        let mut components = [0; N];
+        components[axis] = sign;
+        Vector(components)
+    }
+
+    /// Manhattan distance from the origin: the sum of absolute components.
+    pub(crate) fn dist(self) -> i32 {
+        self.0.iter().map(|c| c.abs()).sum()
     }
 }
 
-impl Add for Position {
-    type Output = Position;
-    fn add(self, rhs: Position) -> Self::Output {
-        Position(self.0 + rhs.0, self.1 + rhs.1)
+impl<const N: usize> AddAssign for Vector<N> {
+    fn add_assign(&mut self, other: Vector<N>) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) { *a += *b; }
     }
 }
 
-impl Sub for Position {
-    type Output = Position;
-    fn sub(self, rhs: Position) -> Self::Output {
-        Position(self.0 - rhs.0, self.1 - rhs.1)
+impl<const N: usize> Add for Vector<N> {
+    type Output = Vector<N>;
+    fn add(mut self, rhs: Vector<N>) -> Vector<N> {
+        self += rhs;
+        self
+    }
+}
+
+impl<const N: usize> Sub for Vector<N> {
+    type Output = Vector<N>;
+    fn sub(mut self, rhs: Vector<N>) -> Vector<N> {
+        for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) { *a -= *b; }
+        self
     }
 }
 
 impl Position {
-    fn dist(self) -> i32 {
-        self.0.abs() + self.1.abs()
+    /// Construct a 2D position from its `x`/`y` components.
+    pub(crate) fn new(x: i32, y: i32) -> Position {
+        Vector([x, y])
     }
+
+    pub(crate) fn x(self) -> i32 { self.0[0] }
+    pub(crate) fn y(self) -> i32 { self.0[1] }
 }
 
 impl fmt::Display for Position {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {})", self.0, self.1)
+        write!(f, "({}, {})", self.x(), self.y())
+    }
+}
+
+/// A dimension-agnostic heading: a unit step along one `axis` in a direction.
+///
+/// Where `Compass` is hardwired to the 2D plane, `Heading` names an axis index
+/// and a sign, so it yields a step delta in any dimension — enough to drive the
+/// breadcrumb-style intersection detection in `solve2_hash` through 3D space.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct Heading {
+    pub(crate) axis: usize,
+    pub(crate) sign: i32,
+}
+
+impl Heading {
+    pub(crate) fn delta<const N: usize>(self) -> Vector<N> {
+        Vector::unit(self.axis, self.sign)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Turn {
+    Left,
+    Right,
+}
+
+impl FromStr for Turn {
+    type Err = InstructionError;
+    fn from_str(s: &str) -> result::Result<Turn, InstructionError> {
+        match s.chars().next() {
+            Some('L') => Ok(Turn::Left),
+            Some('R') => Ok(Turn::Right),
+            Some(c)   => Err(InstructionError::UnknownTurn(c)),
+            None      => Err(InstructionError::MissingTurn),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Instruction {
+    turn: Turn,
+    distance: i32,
+}
+
+impl FromStr for Instruction {
+    type Err = InstructionError;
+    fn from_str(s: &str) -> result::Result<Instruction, InstructionError> {
+        let s = s.trim();
+        let mut chars = s.chars();
+
+        let first = match chars.next() {
+                Some(c) => c,
+                None    => return Err(InstructionError::MissingTurn),
+            };
+
+        let turn = first.to_string().parse::<Turn>()?;
+        let distance = chars.as_str()
+            .parse::<i32>()
+            .map_err(InstructionError::ParseDistance)?;
+
+        Ok(Instruction { turn: turn, distance: distance })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum InstructionError {
+    UnknownTurn(char),
+    MissingTurn,
+    ParseDistance(ParseIntError),
+}
+
+impl fmt::Display for InstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InstructionError::UnknownTurn(c)       => write!(f, "unknown turn `{}`", c),
+            InstructionError::MissingTurn          => write!(f, "missing turn direction"),
+            InstructionError::ParseDistance(ref e) => write!(f, "invalid distance: {}", e),
+        }
+    }
+}
+
+impl Error for InstructionError {
+    fn description(&self) -> &str {
+        match *self {
+            InstructionError::UnknownTurn(_)   => "unknown turn",
+            InstructionError::MissingTurn      => "missing turn direction",
+            InstructionError::ParseDistance(_) => "invalid distance",
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        match *self {
+            InstructionError::ParseDistance(ref e) => Some(e),
+            _ => None,
+        }
     }
 }
 
+// Lazily split a command string on commas, trimming whitespace, and parse each
+// token into an `Instruction`.  Callers can short-circuit on the first
+// malformed token with `?`.
+fn parse_instructions<'a>(input: &'a str)
+    -> impl Iterator<Item = result::Result<Instruction, InstructionError>> + 'a
+{
+    input.split(',')
+        .map(str::trim)
+        .filter(|cmd| !cmd.is_empty())
+        .map(|cmd| cmd.parse::<Instruction>())
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 struct State {
     compass: Compass,
@@ -113,29 +273,18 @@ struct State {
 }
 
 impl State {
-    fn process_cmd(&mut self, cmd: &str) -> Result<()> {
-        let mut chars = cmd.trim_left().chars();
-
-        self.compass = match chars.next() {
-                Some('L') => self.compass.turn_left(),
-                Some('R') => self.compass.turn_right(),
-                Some(c) => return Err(format!("invalid turn `{}`", c)),
-                None    => return Ok(()),
-            };
-
-        let dist = match chars.as_str().parse::<i32>() {
-                Ok(n)    => n,
-                Err(err) => return Err(err.description().into()),
+    fn apply(&mut self, inst: Instruction) {
+        self.compass = match inst.turn {
+                Turn::Left  => self.compass.turn_left(),
+                Turn::Right => self.compass.turn_right(),
             };
 
         self.position += match self.compass {
-                Compass::North => Position(0, dist),
-                Compass::West  => Position(-dist, 0),
-                Compass::South => Position(0, -dist),
-                Compass::East  => Position(dist, 0),
+                Compass::North => Position::new(0, inst.distance),
+                Compass::West  => Position::new(-inst.distance, 0),
+                Compass::South => Position::new(0, -inst.distance),
+                Compass::East  => Position::new(inst.distance, 0),
             };
-
-        Ok(())
     }
 }
 
@@ -153,9 +302,9 @@ impl Add for State {
 
         match self.compass {
             Compass::North => state!(self, rhs, rhs.position),
-            Compass::West =>  state!(self, rhs, Position(-rhs.position.1,  rhs.position.0)),  //(x,y) -> (-y, x)
-            Compass::South => state!(self, rhs, Position(-rhs.position.0, -rhs.position.1)), //(x,y) -> (-x, -y)
-            Compass::East =>  state!(self, rhs, Position( rhs.position.1, -rhs.position.0)),  //(x,y) -> (y, -x)
+            Compass::West =>  state!(self, rhs, Position::new(-rhs.position.y(),  rhs.position.x())),  //(x,y) -> (-y, x)
+            Compass::South => state!(self, rhs, Position::new(-rhs.position.x(), -rhs.position.y())), //(x,y) -> (-x, -y)
+            Compass::East =>  state!(self, rhs, Position::new( rhs.position.y(), -rhs.position.x())),  //(x,y) -> (y, -x)
         }
     }
 }
@@ -169,13 +318,27 @@ pub fn run() {
             }
         };
 
-    match solve1_seq(&data) {
+    let seq = solve1_seq(&data);
+    match &seq {
         Ok(s) =>
             println!("The map ends at {}, which {} units away.",
                 s.position, s.position.dist()),
         Err(err) => println!("Error: {}", err),
     };
 
+    // Directional-Dijkstra demo: find a minimum-cost uniform-tile route from
+    // the origin to the endpoint `solve1_seq` reached, with a short max run
+    // length so the route has to turn along the way.
+    if let Ok(ref s) = seq {
+        let start = pathfind::State::new(Position::new(0, 0), Compass::North);
+        match pathfind::shortest_path(start, s.position, |_| 1, 1, 3) {
+            Some((cost, _path)) =>
+                println!("Directional-Dijkstra reaches {} at cost {}", s.position, cost),
+            None =>
+                println!("Directional-Dijkstra found no route to {}", s.position),
+        }
+    }
+
     match solve2_hash(&data) {
         Ok(s) =>
             println!("Our first point of intersection is at {}, \
@@ -192,6 +355,28 @@ pub fn run() {
     }
 
     println!("Lin solutions finds intersecion at {}", solve2_lin(&data));
+
+    // Multi-path wire-crossing mode: treat each input line as an independent
+    // path and report where the paths cross.
+    let paths: Vec<Path> = data.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| Path::new(line).ok())
+        .collect();
+
+    let segments: usize = paths.iter().map(|p| p.segments().len()).sum();
+    println!("Loaded {} path(s) spanning {} segments", paths.len(), segments);
+
+    match closest_crossing(&paths) {
+        Some(pos) => println!("Closest crossing at {}, which is {} units away",
+                              pos, pos.dist()),
+        None      => println!("No crossing found between paths"),
+    }
+
+    match fewest_combined_steps(&paths) {
+        Some((pos, steps)) => println!("Fewest-steps crossing at {} in {} combined steps",
+                                       pos, steps),
+        None               => println!("No crossing found between paths"),
+    }
 }
 
 fn import_data(f: &str) -> Result<String> {
@@ -208,16 +393,11 @@ fn import_data(f: &str) -> Result<String> {
     Ok(data)
 }
 
-fn solve1_seq(input: &str) -> Result<State> {
-    if input.is_empty() {
-        return Ok(State::default())
-    }
-
-    let cmds = input.split(',');
+fn solve1_seq(input: &str) -> result::Result<State, InstructionError> {
     let mut state = State::default();
 
-    for cmd in cmds {
-        state.process_cmd(cmd)?;
+    for inst in parse_instructions(input) {
+        state.apply(inst?);
     }
 
     Ok(state)
@@ -265,25 +445,19 @@ fn solve1_par(input: &str) -> Result<Position> {
 
 use fnv::FnvHashSet;
 
-fn solve2_hash(input: &str) -> Result<Position> {
-    let cmds = input.split(',');
+fn solve2_hash(input: &str) -> result::Result<Position, InstructionError> {
     let mut state  = State::default();
 
     let n = input.len()/4;
     let mut crumbs: FnvHashSet<Position> = FnvHashSet::with_capacity_and_hasher(n, Default::default());
-    crumbs.insert(Position(0,0));
+    crumbs.insert(Position::new(0,0));
 
-    for cmd in cmds {
+    for inst in parse_instructions(input) {
         let mut current = state.position;
-        state.process_cmd(cmd)?;
+        state.apply(inst?);
 
         // insert breadcrumbs into hashset.
-        let delta = match state.compass {
-                Compass::North => Position( 0, 1),
-                Compass::West  => Position(-1, 0),
-                Compass::South => Position( 0,-1),
-                Compass::East  => Position( 1, 0),
-            };
+        let delta = step_delta(state.compass);
 
         let dist = (current - state.position).dist().abs() as u32;
 
@@ -299,69 +473,170 @@ fn solve2_hash(input: &str) -> Result<Position> {
     Ok(state.position)
 }
 
-#[derive(Clone, Copy, Debug)]
-struct Line(Position, Position);
+// A single replayed walk.  Besides the ordered segments we keep the same
+// breadcrumb set `solve2_hash` uses, plus the first-visit step count for every
+// lattice cell, so several paths can be compared for crossings.
+pub(crate) struct Path {
+    segments: Vec<Line>,
+    cells: FnvHashSet<Position>,
+    steps: HashMap<Position, u32>,
+}
 
-trait Intersects {
-    fn intersects(&self) -> Option<Position>;
+impl Path {
+    pub(crate) fn new(input: &str) -> result::Result<Path, InstructionError> {
+        let mut state = State::default();
+        let mut segments = Vec::new();
+        let mut cells: FnvHashSet<Position> = Default::default();
+        let mut steps: HashMap<Position, u32> = HashMap::new();
+
+        let mut current = state.position;
+        let mut walked: u32 = 0;
+        cells.insert(current);
+        steps.insert(current, 0);
+
+        for inst in parse_instructions(input) {
+            let start = state.position;
+            state.apply(inst?);
+
+            let delta = step_delta(state.compass);
+
+            let dist = (start - state.position).dist().abs() as u32;
+            for _ in 0..dist {
+                current += delta;
+                walked += 1;
+                cells.insert(current);
+                // Keep the *first* time we reach a cell.
+                steps.entry(current).or_insert(walked);
+            }
+
+            segments.push(Line(start, state.position));
+        }
+
+        Ok(Path { segments, cells, steps })
+    }
+
+    // The ordered segments walked by this path, in replay order.
+    pub(crate) fn segments(&self) -> &[Line] {
+        &self.segments
+    }
 }
 
-impl Intersects for (Line, Line) {
-    // Assumption: The two lines are perpendicular.
-    // NB: We don't check for intersection of the first point of the second line.
-    fn intersects(&self) -> Option<Position> {
-        //         .
-        //         |
-        // .-------|----.
-        //         .
+// Cells visited by at least two distinct paths, excluding the shared origin.
+fn crossings(paths: &[Path]) -> Vec<Position> {
+    let mut counts: HashMap<Position, u32> = HashMap::new();
+    for path in paths {
+        for &cell in &path.cells {
+            *counts.entry(cell).or_insert(0) += 1;
+        }
+    }
+
+    counts.into_iter()
+        .filter(|&(cell, n)| n >= 2 && cell != Position::new(0, 0))
+        .map(|(cell, _)| cell)
+        .collect()
+}
 
-        // we want to assume the first line is horizontal
-        let canonical = ((self.0).0).0 == ((self.0).1).0;
-        let vert = if canonical { self.0 } else { self.1 };
-        let horz = if canonical { self.1 } else { self.0 };
+// The crossing nearest the origin by Manhattan distance.
+pub(crate) fn closest_crossing(paths: &[Path]) -> Option<Position> {
+    crossings(paths).into_iter().min_by_key(|p| p.dist())
+}
 
-        // horz y is between vert ys
-        let c1 = ((horz.0).1 <= max((vert.0).1, (vert.1).1))
-            && ((horz.0).1 >= min((vert.0).1, (vert.1).1));
+// The crossing minimizing the combined step count each path walks to reach it.
+pub(crate) fn fewest_combined_steps(paths: &[Path]) -> Option<(Position, u32)> {
+    crossings(paths).into_iter()
+        .map(|p| {
+            let total: u32 = paths.iter()
+                .filter_map(|path| path.steps.get(&p))
+                .sum();
+            (p, total)
+        })
+        .min_by_key(|&(_, steps)| steps)
+}
 
-        // vert x is between horz xs
-        let c2 = ((vert.0).0 <= max((horz.0).0, (horz.1).0))
-            && ((vert.0).0 >= min((horz.0).0, (horz.1).0));
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Line(Position, Position);
+
+// A point of intersection that may not land on the integer lattice.
+//
+// Perpendicular grid walks always cross on grid points, but the general
+// parametric solver divides in `f64`, so an intersection can fall between
+// cells.  `Lattice` records the exact integer case; `Rational` keeps the
+// floating-point coordinates otherwise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PreciseIntersection {
+    Lattice(Position),
+    Rational(f64, f64),
+}
 
-        // println!("horz: {:?},  vert: {:?}", horz, vert);
-        // println!("{:?}, {:?}", c1, c2);
+// 2D cross product of two direction vectors: r.x*s.y - r.y*s.x.
+fn cross(r: Position, s: Position) -> i32 {
+    r.x() * s.y() - r.y() * s.x()
+}
 
-        // we determined a point of intersection, then the
-        // intersection can be determined by the y value
-        // of the horizontal line, and x value of the vert
-        if c1 && c2 {
-            Some(Position((vert.0).0, (horz.0).1))
-        } else { None }
+trait Intersects {
+    fn intersects_precise(&self) -> Option<PreciseIntersection>;
+}
+
+impl Intersects for (Line, Line) {
+    // General segment intersection via the cross-product parametric method.
+    //
+    // Line A runs from `p` to `p+r`, line B from `q` to `q+s`.  With
+    // `rxs = cross(r, s)` the segments are parallel when `rxs == 0` (we leave
+    // the collinear-overlap case to the caller and return `None`).  Otherwise
+    // `t = cross(q-p, s) / rxs` and `u = cross(q-p, r) / rxs` locate the hit,
+    // which lies on both segments iff `0 <= t <= 1` and `0 <= u <= 1`.  The
+    // point is `p + t*r`; we emit a `Lattice` position only when it lands on
+    // an integer coordinate.
+    fn intersects_precise(&self) -> Option<PreciseIntersection> {
+        let (a, b) = *self;
+        let p = a.0;
+        let r = a.1 - a.0;
+        let q = b.0;
+        let s = b.1 - b.0;
+
+        let rxs = cross(r, s);
+        if rxs == 0 { return None }
+
+        let qp = q - p;
+        let t = cross(qp, s) as f64 / rxs as f64;
+        let u = cross(qp, r) as f64 / rxs as f64;
+
+        if t < 0.0 || t > 1.0 || u < 0.0 || u > 1.0 { return None }
+
+        let x = p.x() as f64 + t * r.x() as f64;
+        let y = p.y() as f64 + t * r.y() as f64;
+
+        if x.fract() == 0.0 && y.fract() == 0.0 {
+            Some(PreciseIntersection::Lattice(Position::new(x as i32, y as i32)))
+        } else {
+            Some(PreciseIntersection::Rational(x, y))
+        }
     }
 }
 
 fn solve2_lin(input: &str) -> Position {
-    let cmds = input.split(',');
     let mut state = State::default();
 
     let mut history: Vec<Position> = Vec::with_capacity(input.len()/4);
 
-    for cmd in cmds {
+    for inst in parse_instructions(input).filter_map(|r| r.ok()) {
         let current = state;
-        state.process_cmd(cmd);
+        state.apply(inst);
 
         let orient = match current.compass {
                 Compass::North | Compass::South => 1,
                 Compass::East  | Compass::West  => 0,
             };
 
-        let mut prev = Position(0,0);
+        let mut prev = Position::new(0,0);
         let line = Line(current.position, state.position);
 
         for (idx, &hist) in history.iter().enumerate() {
             if idx % 2 != orient { prev = hist; continue; }
             let connect = Line(prev, hist);
-            if let Some(p) = (connect, line).intersects() {
+            // The general parametric solver subsumes the old perpendicular-only
+            // check, so this is where that machinery earns its keep outside tests.
+            if let Some(PreciseIntersection::Lattice(p)) = (connect, line).intersects_precise() {
                 if p == line.0 { continue }
                 return p
             }
@@ -377,26 +652,27 @@ fn solve2_lin(input: &str) -> Position {
 
 #[cfg(test)]
 mod test {
-    use super::{ State, Compass, Position, solve1_par, solve1_seq, import_data, Line, Intersects, solve2_hash, solve2_lin };
+    use super::{ State, Compass, Position, Vector, Heading, solve1_par, solve1_seq, import_data, Line, Intersects, PreciseIntersection, solve2_hash, solve2_lin, Path, closest_crossing, fewest_combined_steps, Instruction, Turn, InstructionError, parse_instructions };
+    use std::collections::HashSet;
     use test::Bencher;
 
     #[test]
     fn state_add() {
         let r = State {
             compass: Compass::East,
-            position: Position(1, 0),
+            position: Position::new(1, 0),
         };
 
         let l = State {
             compass: Compass::West,
-            position: Position(-1, 0),
+            position: Position::new(-1, 0),
         };
 
         // Assert that binary addition works on L/R:
-        assert_eq!(r + r, State { compass: Compass::South, position: Position(1, -1) });
-        assert_eq!(r + l, State { compass: Compass::North, position: Position(1, 1) });
-        assert_eq!(l + r, State { compass: Compass::North, position: Position(-1, 1) });
-        assert_eq!(l + l, State { compass: Compass::South, position: Position(-1, -1) });
+        assert_eq!(r + r, State { compass: Compass::South, position: Position::new(1, -1) });
+        assert_eq!(r + l, State { compass: Compass::North, position: Position::new(1, 1) });
+        assert_eq!(l + r, State { compass: Compass::North, position: Position::new(-1, 1) });
+        assert_eq!(l + l, State { compass: Compass::South, position: Position::new(-1, -1) });
 
         println!("");
         println!("Addition trait:");
@@ -414,26 +690,119 @@ mod test {
     }
 
     #[test]
-    fn line_intersects() {
-        macro_rules! p { ($x:expr, $y:expr) => (Position($x, $y)) }
+    fn line_intersects_precise() {
+        macro_rules! p { ($x:expr, $y:expr) => (Position::new($x, $y)) }
 
+        // Perpendicular segments still resolve to a lattice point.
         let l1 = Line(p!(-1,0), p!(1,0));
         let l2 = Line(p!(0,-1), p!(0,1));
+        assert_eq!((l1,l2).intersects_precise(),
+                   Some(PreciseIntersection::Lattice(Position::new(0,0))));
+        assert_eq!((l2,l1).intersects_precise(),
+                   Some(PreciseIntersection::Lattice(Position::new(0,0))));
 
-        assert_eq!((l1,l2).intersects(), Some(Position(0,0)));
-        assert_eq!((l2,l1).intersects(), Some(Position(0,0)));
-
+        // Same crossing, segments walked in the opposite direction.
         let l1 = Line(p!(1,0), p!(-1,0));
         let l2 = Line(p!(0,1), p!(0,-1));
-
-        assert_eq!((l1,l2).intersects(), Some(Position(0,0)));
-        assert_eq!((l2,l1).intersects(), Some(Position(0,0)));
-
+        assert_eq!((l1,l2).intersects_precise(),
+                   Some(PreciseIntersection::Lattice(Position::new(0,0))));
+        assert_eq!((l2,l1).intersects_precise(),
+                   Some(PreciseIntersection::Lattice(Position::new(0,0))));
+
+        // Diagonal crossing that lands off the lattice.
+        let l1 = Line(p!(0,0), p!(2,2));
+        let l2 = Line(p!(0,1), p!(1,0));
+        assert_eq!((l1,l2).intersects_precise(),
+                   Some(PreciseIntersection::Rational(0.5, 0.5)));
+
+        // Parallel segments never intersect.
+        let l1 = Line(p!(0,0), p!(2,0));
+        let l2 = Line(p!(0,1), p!(2,1));
+        assert_eq!((l1,l2).intersects_precise(), None);
+
+        // Perpendicular, but out of each other's range.
         let l1 = Line(p!(-2, -16), p!(-2, -17));
         let l2 = Line(p!(-16, -17), p!(-14, -17));
+        assert_eq!((l1,l2).intersects_precise(), None);
+        assert_eq!((l2,l1).intersects_precise(), None);
+    }
+
+    #[test]
+    fn parse_instructions_ok_and_errors() {
+        let good: Vec<_> = parse_instructions("R2, L3 ")
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(good, vec![
+            Instruction { turn: Turn::Right, distance: 2 },
+            Instruction { turn: Turn::Left,  distance: 3 },
+        ]);
+
+        match parse_instructions("X5").next().unwrap() {
+            Err(InstructionError::UnknownTurn('X')) => {}
+            other => panic!("expected UnknownTurn, got {:?}", other),
+        }
+
+        match parse_instructions("Rfoo").next().unwrap() {
+            Err(InstructionError::ParseDistance(_)) => {}
+            other => panic!("expected ParseDistance, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn path_crossings() {
+        // Disjoint walks into opposite quadrants share only the origin.
+        let a = Path::new("R2, L2").unwrap();  // east to (2,0), north to (2,2)
+        let b = Path::new("L2, R2").unwrap();  // west to (-2,0), north to (-2,2)
+        assert_eq!(closest_crossing(&[a, b]), None);
+
+        // `a` runs east along y=0 through (1,0) and (2,0).
+        // `b` starts east to (1,0), climbs north, then comes back down to (2,0),
+        // so the two share (1,0) and (2,0).
+        let a = Path::new("R2").unwrap();
+        let b = Path::new("R1, L2, R1, R2").unwrap();
+
+        // `a` is a single eastward segment; `b` replays four.
+        assert_eq!(a.segments().len(), 1);
+        assert_eq!(b.segments().len(), 4);
+
+        let paths = [a, b];
+
+        // (1,0) is closer to the origin than (2,0).
+        assert_eq!(closest_crossing(&paths), Some(Position::new(1, 0)));
+
+        // `a` reaches (1,0) in 1 step; `b` reaches it in 1 step -> combined 2.
+        // (2,0) costs `a` 2 steps and `b` 6 steps -> combined 8, so (1,0) wins.
+        assert_eq!(fewest_combined_steps(&paths), Some((Position::new(1, 0), 2)));
+    }
+
+    #[test]
+    fn vector_n_dimensional() {
+        // 3D arithmetic and Manhattan distance carry over from the 2D case.
+        let a: Vector<3> = Vector([1, -2, 3]);
+        let b: Vector<3> = Vector([0,  2, -1]);
+        assert_eq!(a + b, Vector([1, 0, 2]));
+        assert_eq!((a - b).dist(), 1 + 4 + 4);
+
+        // A heading yields a unit step along its axis in any dimension.
+        let up = Heading { axis: 2, sign: 1 };
+        assert_eq!(up.delta::<3>(), Vector([0, 0, 1]));
+
+        // Breadcrumb-style self-intersection, as in `solve2_hash`, but in 3D:
+        // walk two tiles up the z axis and three back down, revisiting (0,0,1).
+        let up = Heading { axis: 2, sign:  1 }.delta::<3>();
+        let down = Heading { axis: 2, sign: -1 }.delta::<3>();
+
+        let mut seen: HashSet<Vector<3>> = HashSet::new();
+        let mut pos: Vector<3> = Vector::default();
+        seen.insert(pos);
+
+        let mut revisit = None;
+        for mv in [up, up, down, down, down].iter() {
+            pos += *mv;
+            if !seen.insert(pos) { revisit = Some(pos); break; }
+        }
 
-        assert_eq!((l1,l2).intersects(), None);
-        assert_eq!((l2,l1).intersects(), None);
+        assert_eq!(revisit, Some(Vector([0, 0, 1])));
     }
 
     #[bench]
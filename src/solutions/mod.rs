@@ -0,0 +1,2 @@
+pub mod day1;
+pub mod pathfind;
@@ -0,0 +1,181 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use super::day1::{Position, Compass, step_delta};
+
+// How many consecutive tiles we have walked in the current heading.
+pub(crate) type Run = u8;
+
+// A search state for the directional Dijkstra: where the walker stands, which
+// way it faces, and how long it has been going straight.  The heading and run
+// length are part of the state because the cost of a move depends on them — a
+// turn is only legal after `min_run` straight tiles and we may not continue
+// past `max_run`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct State {
+    pub(crate) position: Position,
+    pub(crate) compass: Compass,
+    pub(crate) run_length: Run,
+}
+
+impl State {
+    pub(crate) fn new(position: Position, compass: Compass) -> State {
+        State { position: position, compass: compass, run_length: 0 }
+    }
+}
+
+// A min-heap entry ordered by accumulated cost.  `BinaryHeap` is a max-heap,
+// so `Ord` is reversed to pop the cheapest frontier state first.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Node {
+    cost: u32,
+    state: State,
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Node) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Expand a state into its legal successors: continue straight while under
+// `max_run`, or turn left/right once we have gone straight for at least
+// `min_run` tiles.  Turning resets the run to one and can never produce a
+// 180 degree reversal.
+fn successors(state: State, min_run: Run, max_run: Run) -> Vec<State> {
+    let mut out = Vec::with_capacity(3);
+
+    if state.run_length < max_run {
+        out.push(State {
+            position: state.position + step_delta(state.compass),
+            compass: state.compass,
+            run_length: state.run_length + 1,
+        });
+    }
+
+    if state.run_length >= min_run {
+        let left = state.compass.turn_left();
+        out.push(State {
+            position: state.position + step_delta(left),
+            compass: left,
+            run_length: 1,
+        });
+
+        let right = state.compass.turn_right();
+        out.push(State {
+            position: state.position + step_delta(right),
+            compass: right,
+            run_length: 1,
+        });
+    }
+
+    out
+}
+
+fn reconstruct(prev: &HashMap<State, State>, start: State, end: State) -> Vec<State> {
+    let mut path = vec![end];
+    let mut cur = end;
+
+    while cur != start {
+        match prev.get(&cur) {
+            Some(&p) => { path.push(p); cur = p; }
+            None     => break,
+        }
+    }
+
+    path.reverse();
+    path
+}
+
+/// Find a minimum-cost route from `start` to `target` on a weighted grid.
+///
+/// `cost` returns the cost of stepping onto a given tile (the start tile is
+/// free).  `min_run`/`max_run` bound how many consecutive tiles may be walked
+/// in a single heading: a turn is only permitted once the run reaches
+/// `min_run`, and the walk may not exceed `max_run` straight tiles, so the
+/// target is only accepted when approached on a run of at least `min_run`.
+///
+/// Returns the optimal cost together with the reconstructed path, or `None`
+/// when the target is unreachable under the constraints.
+pub(crate) fn shortest_path<F>(start: State,
+                        target: Position,
+                        cost: F,
+                        min_run: Run,
+                        max_run: Run)
+    -> Option<(u32, Vec<State>)>
+    where F: Fn(Position) -> u32
+{
+    let mut dist: HashMap<State, u32> = HashMap::new();
+    let mut prev: HashMap<State, State> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    dist.insert(start, 0);
+    heap.push(Node { cost: 0, state: start });
+
+    while let Some(Node { cost: g, state }) = heap.pop() {
+        if state.position == target && state.run_length >= min_run {
+            return Some((g, reconstruct(&prev, start, state)));
+        }
+
+        // Skip states we have already settled with a cheaper cost.
+        if let Some(&best) = dist.get(&state) {
+            if g > best { continue }
+        }
+
+        for next in successors(state, min_run, max_run) {
+            let ncost = g + cost(next.position);
+            let improved = match dist.get(&next) {
+                    Some(&best) => ncost < best,
+                    None        => true,
+                };
+
+            if improved {
+                dist.insert(next, ncost);
+                prev.insert(next, state);
+                heap.push(Node { cost: ncost, state: next });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ State, shortest_path };
+    use super::super::day1::{ Position, Compass };
+
+    #[test]
+    fn straight_run_limited() {
+        // A uniform-cost open field: every tile costs one.  With no run limit
+        // to speak of, the cheapest route east is a straight line.
+        let start = State::new(Position::new(0, 0), Compass::East);
+        let target = Position::new(3, 0);
+
+        let (cost, path) = shortest_path(start, target, |_| 1, 1, 10).unwrap();
+
+        assert_eq!(cost, 3);
+        assert_eq!(path.first().unwrap().position, Position::new(0, 0));
+        assert_eq!(path.last().unwrap().position, target);
+    }
+
+    #[test]
+    fn max_run_forces_turns() {
+        // With `max_run == 1` the walker must turn after every tile, so it can
+        // only reach tiles along a staircase.  One east step then one north
+        // step lands on (1, 1) at a cost of two.
+        let start = State::new(Position::new(0, 0), Compass::East);
+        let target = Position::new(1, 1);
+
+        let (cost, path) = shortest_path(start, target, |_| 1, 1, 1).unwrap();
+
+        assert_eq!(cost, 2);
+        assert_eq!(path.last().unwrap().position, target);
+    }
+}